@@ -0,0 +1,66 @@
+use git2::{Cred, FetchOptions, RemoteCallbacks, Repository};
+use std::{collections::HashMap, path::Path};
+
+fn ssh_credentials(username: &str) -> Result<Cred, git2::Error> {
+    // Prefer the agent: this callback runs on a blocking task while the TUI's
+    // raw-mode stdin reader is also live, so there is no safe way to prompt
+    // for a passphrase interactively. `Cred::ssh_key` below doesn't read the
+    // key file at all, so it can't tell a passphrase-protected key from an
+    // unprotected one up front — trying it first would hand libgit2 a
+    // credential that's silently unusable for the common encrypted-key case.
+    if let Ok(cred) = Cred::ssh_key_from_agent(username) {
+        return Ok(cred);
+    }
+
+    let home = std::env::var("HOME").unwrap_or_default();
+
+    for key in ["id_ed25519", "id_rsa"] {
+        let path = Path::new(&home).join(".ssh").join(key);
+        if path.exists() {
+            return Cred::ssh_key(username, None, &path, None);
+        }
+    }
+
+    Cred::ssh_key_from_agent(username)
+}
+
+/// Fetches the given base branches from `origin` and returns the OID each one
+/// resolved to, keyed by branch name. Replaces the one-branch-at-a-time
+/// `gh api` lookup with a single native fetch covering every base the open
+/// PRs actually target.
+pub fn fetch_base_branches(
+    base_names: &[String],
+) -> Result<HashMap<String, String>, Box<dyn std::error::Error>> {
+    let repo = Repository::discover(".")?;
+    let mut remote = repo.find_remote("origin")?;
+
+    let mut callbacks = RemoteCallbacks::new();
+    callbacks.credentials(|_url, username_from_url, _allowed| {
+        ssh_credentials(username_from_url.unwrap_or("git"))
+    });
+
+    let mut fetch_options = FetchOptions::new();
+    fetch_options.remote_callbacks(callbacks);
+
+    let refspecs = base_names
+        .iter()
+        .map(|name| format!("+refs/heads/{name}:refs/remotes/origin/{name}"))
+        .collect::<Vec<_>>();
+
+    remote.fetch(&refspecs, Some(&mut fetch_options), None)?;
+
+    let mut branches = HashMap::new();
+    for name in base_names {
+        // Resolve each base independently: one branch missing from the
+        // remote (deleted/renamed upstream, or a PR targeting something the
+        // fetch didn't bring down) shouldn't stale out every other PR's
+        // Behind/Unsynced status.
+        if let Ok(reference) = repo.find_reference(&format!("refs/remotes/origin/{name}")) {
+            if let Some(oid) = reference.target() {
+                branches.insert(name.clone(), oid.to_string());
+            }
+        }
+    }
+
+    Ok(branches)
+}
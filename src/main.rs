@@ -1,3 +1,4 @@
+mod git;
 mod models;
 
 use clap::Parser;
@@ -8,26 +9,52 @@ use crossterm::{
 };
 use futures::StreamExt;
 use itertools::Itertools as _;
-use models::{Branch, Mergeable, PullRequest, Repo};
+use models::{CheckConclusion, CheckData, CheckProgress, CheckStatus, Mergeable, PullRequest, Repo};
+use notify_rust::Notification;
 use ratatui::{
     prelude::*,
-    widgets::{Block, Cell, Row, Table, TableState},
+    widgets::{
+        Block, Cell, Clear, List, ListItem, ListState, Paragraph, Row, Table, TableState, Wrap,
+    },
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    future::Future,
+    time::{Duration, Instant},
 };
-use std::{collections::HashMap, time::Duration};
 use tokio::{
     process::Command as AsyncCommand,
     sync::broadcast,
-    task::JoinSet,
+    task::{AbortHandle, JoinSet},
     time::{interval, MissedTickBehavior},
 };
 
 const TICK_INTERVAL: Duration = Duration::from_millis(50);
 const AUTO_UPDATE: Duration = Duration::from_secs(60);
+const JOB_RETENTION: Duration = Duration::from_secs(3);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum JobState {
+    Queued,
+    Running,
+    Done,
+    Failed,
+}
+
+#[derive(Debug, Clone)]
+struct Job {
+    label: String,
+    state: JobState,
+    finished_at: Option<Instant>,
+}
 
 #[derive(Debug, Clone, Default)]
 struct AppState {
     prs: Vec<PullRequest>,
     branches: HashMap<String, String>,
+    diffs: HashMap<i32, String>,
+    check_states: HashMap<i32, CheckProgress>,
+    jobs: BTreeMap<u64, Job>,
     error_message: Option<String>,
     done: bool,
 }
@@ -39,23 +66,169 @@ struct App {
     state: AppState,
     table_state: TableState,
     tasks: JoinSet<()>,
+    show_diff: bool,
+    diff_scroll: u16,
+    expanded: Option<usize>,
+    check_index: Option<usize>,
+    action_menu: Option<usize>,
+    action_index: usize,
+    notify: bool,
+    next_job_id: u64,
+    abort_handles: HashMap<u64, AbortHandle>,
+    spinner_frame: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum UpdateStrategy {
+    Rebase,
+    Merge,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum MergeStrategy {
+    Merge,
+    Squash,
+    Rebase,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PrAction {
+    UpdateBranch(UpdateStrategy),
+    Complete(MergeStrategy),
+}
+
+impl PrAction {
+    const ALL: [PrAction; 5] = [
+        PrAction::UpdateBranch(UpdateStrategy::Rebase),
+        PrAction::UpdateBranch(UpdateStrategy::Merge),
+        PrAction::Complete(MergeStrategy::Merge),
+        PrAction::Complete(MergeStrategy::Squash),
+        PrAction::Complete(MergeStrategy::Rebase),
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            PrAction::UpdateBranch(UpdateStrategy::Rebase) => "Update branch (rebase)",
+            PrAction::UpdateBranch(UpdateStrategy::Merge) => "Update branch (merge)",
+            PrAction::Complete(MergeStrategy::Merge) => "Merge",
+            PrAction::Complete(MergeStrategy::Squash) => "Squash and merge",
+            PrAction::Complete(MergeStrategy::Rebase) => "Rebase and merge",
+        }
+    }
+
+    fn build_command(&self, repo: &str, number: &str) -> AsyncCommand {
+        let mut command = AsyncCommand::new("gh");
+        command.arg("pr");
+
+        match self {
+            PrAction::UpdateBranch(strategy) => {
+                command.arg("update-branch").arg(match strategy {
+                    UpdateStrategy::Rebase => "--rebase",
+                    UpdateStrategy::Merge => "--merge",
+                });
+            }
+            PrAction::Complete(strategy) => {
+                command.arg("merge").arg(match strategy {
+                    MergeStrategy::Merge => "--merge",
+                    MergeStrategy::Squash => "--squash",
+                    MergeStrategy::Rebase => "--rebase",
+                });
+            }
+        }
+
+        command.arg("-R").arg(repo).arg(number);
+        // Cancelling a job only stops polling this future (see
+        // `App::cancel_latest_job`); without this, an aborted future would
+        // leave the `gh` child running and able to complete the merge anyway.
+        command.kill_on_drop(true);
+        command
+    }
+}
+
+#[test]
+fn test_build_command_args() {
+    fn args(action: PrAction) -> Vec<String> {
+        action
+            .build_command("owner/repo", "42")
+            .as_std()
+            .get_args()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    assert_eq!(
+        args(PrAction::UpdateBranch(UpdateStrategy::Rebase)),
+        ["pr", "update-branch", "--rebase", "-R", "owner/repo", "42"]
+    );
+    assert_eq!(
+        args(PrAction::UpdateBranch(UpdateStrategy::Merge)),
+        ["pr", "update-branch", "--merge", "-R", "owner/repo", "42"]
+    );
+    assert_eq!(
+        args(PrAction::Complete(MergeStrategy::Merge)),
+        ["pr", "merge", "--merge", "-R", "owner/repo", "42"]
+    );
+    assert_eq!(
+        args(PrAction::Complete(MergeStrategy::Squash)),
+        ["pr", "merge", "--squash", "-R", "owner/repo", "42"]
+    );
+    assert_eq!(
+        args(PrAction::Complete(MergeStrategy::Rebase)),
+        ["pr", "merge", "--rebase", "-R", "owner/repo", "42"]
+    );
 }
 
 #[derive(Debug, Clone)]
 enum AppEvent {
     FetchedPRs(Vec<PullRequest>),
-    FetchedBranchCommit(String, String),
+    FetchedBranches(HashMap<String, String>),
+    FetchedDiff(i32, String),
+    JobProgress { id: u64, label: String, state: JobState },
     Error(String),
 }
 
+/// Handle given to a spawned job so it can report its own lifecycle.
+struct JobHandle {
+    sender: broadcast::Sender<AppEvent>,
+    id: u64,
+    label: String,
+}
+
+impl JobHandle {
+    fn report(&self, state: JobState) {
+        let _ = self.sender.send(AppEvent::JobProgress {
+            id: self.id,
+            label: self.label.clone(),
+            state,
+        });
+    }
+
+    fn running(&self) {
+        self.report(JobState::Running);
+    }
+
+    fn done(&self) {
+        self.report(JobState::Done);
+    }
+
+    fn failed(&self) {
+        self.report(JobState::Failed);
+    }
+}
+
 #[derive(Parser)]
 struct Cli {
     #[clap(short = 'R', long)]
     repo: Option<String>,
+
+    /// Send a desktop notification when a PR's CI checks start failing or go
+    /// from pending to passing
+    #[clap(long)]
+    notify: bool,
 }
 
 impl App {
-    fn new(repo: String) -> Self
+    fn new(repo: String, notify: bool) -> Self
 where {
         let (sender, receiver) = broadcast::channel(32);
         Self {
@@ -65,6 +238,64 @@ where {
             state: AppState::default(),
             table_state: TableState::default(),
             tasks: JoinSet::new(),
+            show_diff: false,
+            diff_scroll: 0,
+            expanded: None,
+            check_index: None,
+            action_menu: None,
+            action_index: 0,
+            notify,
+            next_job_id: 0,
+            abort_handles: HashMap::new(),
+            spinner_frame: 0,
+        }
+    }
+
+    fn spawn_job<F, Fut>(&mut self, label: impl Into<String>, job: F) -> u64
+    where
+        F: FnOnce(JobHandle) -> Fut,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let id = self.next_job_id;
+        self.next_job_id += 1;
+        let label = label.into();
+
+        let handle = JobHandle {
+            sender: self.sender.clone(),
+            id,
+            label: label.clone(),
+        };
+        handle.report(JobState::Queued);
+
+        let abort_handle = self.tasks.spawn(job(handle));
+        self.abort_handles.insert(id, abort_handle);
+
+        id
+    }
+
+    /// Cancels the most recently started job that's still queued or running.
+    /// There's no explicit job selection in the UI, so this just targets the
+    /// newest one in `state.jobs`.
+    fn cancel_latest_job(&mut self) {
+        let id = self
+            .state
+            .jobs
+            .iter()
+            .rev()
+            .find(|(_, job)| matches!(job.state, JobState::Queued | JobState::Running))
+            .map(|(&id, _)| id);
+
+        let Some(id) = id else {
+            return;
+        };
+
+        if let Some(handle) = self.abort_handles.remove(&id) {
+            handle.abort();
+        }
+
+        if let Some(job) = self.state.jobs.get_mut(&id) {
+            job.state = JobState::Failed;
+            job.finished_at = Some(Instant::now());
         }
     }
 
@@ -80,7 +311,6 @@ where {
 
         let mut events = EventStream::new();
         self.fetch_prs();
-        self.fetch_last_commit("master");
 
         while !self.state.done {
             tokio::select! {
@@ -123,25 +353,63 @@ where {
         ])
     }
 
+    fn check_row(check: &CheckData) -> Row<'static> {
+        let status = match (check.verdict(), check.state()) {
+            (CheckConclusion::Success, _) => Cell::from("SUCCESS".bold().green()),
+            (CheckConclusion::Failure, _) => Cell::from("FAILURE".bold().red()),
+            (_, CheckStatus::InProgress) => Cell::from("IN_PROGRESS".yellow()),
+            (_, CheckStatus::Queued) => Cell::from("QUEUED".yellow()),
+            (CheckConclusion::Skipped, _) => Cell::from("SKIPPED".dark_gray()),
+            _ => Cell::from("UNKNOWN".dark_gray()),
+        };
+
+        Row::new(vec![
+            Cell::from(""),
+            Cell::from(format!("  └ {}", check.name())),
+            Cell::from(""),
+            status,
+        ])
+    }
+
     fn draw(
         &mut self,
         terminal: &mut Terminal<impl Backend>,
     ) -> Result<(), Box<dyn std::error::Error>> {
+        self.state.jobs.retain(|_, job| {
+            !matches!(job.finished_at, Some(at) if at.elapsed() > JOB_RETENTION)
+        });
+        self.spinner_frame = self.spinner_frame.wrapping_add(1);
+
         terminal.draw(|f| {
             let size = f.area();
             let chunks = Layout::default()
                 .direction(Direction::Vertical)
-                .constraints([Constraint::Percentage(100)])
+                .constraints(if self.show_diff {
+                    vec![Constraint::Percentage(50), Constraint::Percentage(50)]
+                } else {
+                    vec![Constraint::Percentage(100)]
+                })
                 .split(size);
 
             let branches = self.state.branches.clone();
 
-            let rows = self
-                .state
-                .prs
-                .iter()
-                .map(|pr| Self::row(&branches, pr))
-                .collect::<Vec<_>>();
+            let selected_check_style = Style::default().add_modifier(Modifier::REVERSED);
+
+            let mut rows = Vec::new();
+            for (i, pr) in self.state.prs.iter().enumerate() {
+                rows.push(Self::row(&branches, pr));
+
+                if self.expanded == Some(i) {
+                    for (j, check) in pr.checks.iter().enumerate() {
+                        let row = Self::check_row(check);
+                        rows.push(if self.check_index == Some(j) {
+                            row.style(selected_check_style)
+                        } else {
+                            row
+                        });
+                    }
+                }
+            }
 
             let header = vec!["ID", "NAME", "BRANCH", "STATUS"]
                 .into_iter()
@@ -158,9 +426,29 @@ where {
             let selected_row_style = Style::default().add_modifier(Modifier::REVERSED);
 
             let mut block = Block::default();
+
+            const SPINNER: [char; 4] = ['|', '/', '-', '\\'];
+            let spinner = SPINNER[self.spinner_frame % SPINNER.len()];
+
+            let active_jobs = self
+                .state
+                .jobs
+                .values()
+                .filter(|job| matches!(job.state, JobState::Queued | JobState::Running))
+                .map(|job| format!("{spinner} {}…", job.label))
+                .collect::<Vec<_>>();
+
+            if !active_jobs.is_empty() {
+                block = block.title_bottom(
+                    Line::from(active_jobs.join("   ")).style(Style::default().fg(Color::Cyan)),
+                );
+            }
+
             if let Some(msg) = &self.state.error_message {
                 block = block.title_bottom(
-                    Line::from(msg.to_owned()).style(Style::default().fg(Color::Red)),
+                    Line::from(msg.to_owned())
+                        .style(Style::default().fg(Color::Red))
+                        .alignment(Alignment::Right),
                 );
             }
 
@@ -169,79 +457,242 @@ where {
                 .row_highlight_style(selected_row_style)
                 .block(block);
 
-            f.render_stateful_widget(table, chunks[0], &mut self.table_state);
+            let mut render_state = self.table_state.clone();
+            if self.expanded.is_some() {
+                render_state.select(None);
+            }
+
+            f.render_stateful_widget(table, chunks[0], &mut render_state);
+
+            if self.show_diff {
+                let title = self
+                    .table_state
+                    .selected()
+                    .and_then(|i| self.state.prs.get(i))
+                    .map(|pr| format!("Diff #{}", pr.number))
+                    .unwrap_or_else(|| "Diff".to_owned());
+
+                let text = self
+                    .table_state
+                    .selected()
+                    .and_then(|i| self.state.prs.get(i))
+                    .and_then(|pr| self.state.diffs.get(&pr.number))
+                    .map(|diff| colorize_diff(diff))
+                    .unwrap_or_default();
+
+                let paragraph = Paragraph::new(text)
+                    .block(Block::bordered().title(title))
+                    .wrap(Wrap { trim: false })
+                    .scroll((self.diff_scroll, 0));
+
+                f.render_widget(paragraph, chunks[1]);
+            }
+
+            if let Some(number) = self
+                .action_menu
+                .and_then(|selected| self.state.prs.get(selected))
+                .map(|pr| pr.number)
+            {
+                let items = PrAction::ALL
+                    .iter()
+                    .map(|action| ListItem::new(action.label()))
+                    .collect::<Vec<_>>();
+
+                let list = List::new(items)
+                    .block(Block::bordered().title(format!("Actions for #{number}")))
+                    .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+                let area = centered_rect(40, 30, size);
+                let mut menu_state = ListState::default();
+                menu_state.select(Some(self.action_index));
+
+                f.render_widget(Clear, area);
+                f.render_stateful_widget(list, area, &mut menu_state);
+            }
         })?;
 
         Ok(())
     }
 
+    fn open_url(&mut self, url: String) {
+        self.spawn_job("open URL", move |job| async move {
+            job.running();
+            let _ = AsyncCommand::new("open")
+                .arg(&url)
+                .kill_on_drop(true)
+                .output()
+                .await;
+            job.done();
+        });
+    }
+
     fn open_externally(&mut self, selected: usize) {
-        let url = self.state.prs[selected].url.to_string();
+        let Some(pr) = self.state.prs.get(selected) else {
+            return;
+        };
 
-        self.tasks.spawn(async move {
-            let _ = AsyncCommand::new("open").arg(url).output().await;
-        });
+        self.open_url(pr.url.clone());
     }
 
-    fn rebase(&mut self, selected: usize) {
-        let pr = &self.state.prs[selected];
+    fn run_action(&mut self, selected: usize, action: PrAction) {
+        let Some(pr) = self.state.prs.get(selected) else {
+            return;
+        };
 
-        if pr.mergeable != Mergeable::Ok {
+        if pr.mergeable != Mergeable::Ok || pr.draft {
             return;
         }
 
-        let sender = self.sender.clone();
         let number = pr.number.to_string();
         let repo = self.repo.clone();
+        let label = format!("{} #{number}", action.label());
+
+        self.spawn_job(label, move |job| async move {
+            job.running();
 
-        self.tasks.spawn(async move {
-            let mut command = AsyncCommand::new("gh");
-            command
-                .arg("pr")
-                .arg("update-branch")
-                .arg("--rebase")
-                .arg("-R")
-                .arg(repo)
-                .arg(number);
+            let mut command = action.build_command(&repo, &number);
 
             match command.output().await {
-                Ok(status) if !status.status.success() => {
-                    let _ = sender.send(AppEvent::Error(
-                        String::from_utf8_lossy(&status.stderr).into(),
-                    ));
+                Ok(status) if status.status.success() => {
+                    job.done();
+                }
+                Ok(status) => {
+                    job.sender
+                        .send(AppEvent::Error(
+                            String::from_utf8_lossy(&status.stderr).into(),
+                        ))
+                        .ok();
+                    job.failed();
                 }
                 Err(e) => {
-                    let _ = sender.send(AppEvent::Error(e.to_string()));
+                    job.sender.send(AppEvent::Error(e.to_string())).ok();
+                    job.failed();
+                }
+            }
+        });
+    }
+
+    fn notify_check_transition(&mut self, number: i32, title: String, status: CheckProgress) {
+        self.tasks.spawn_blocking(move || {
+            let body = match status {
+                CheckProgress::Failure => format!("#{number} {title} — checks failing"),
+                CheckProgress::Success => format!("#{number} {title} — checks passing"),
+                CheckProgress::Pending => format!("#{number} {title} — checks pending"),
+            };
+
+            let _ = Notification::new()
+                .summary("pr-manager")
+                .body(&body)
+                .show();
+        });
+    }
+
+    fn fetch_branches(&mut self) {
+        let base_names = self
+            .state
+            .prs
+            .iter()
+            .map(|pr| pr.base_name.clone())
+            .unique()
+            .collect::<Vec<_>>();
+
+        if base_names.is_empty() {
+            return;
+        }
+
+        self.spawn_job("resolve base branches", move |job| async move {
+            job.running();
+
+            let result = tokio::task::spawn_blocking(move || git::fetch_base_branches(&base_names))
+                .await;
+
+            match result {
+                Ok(Ok(branches)) => {
+                    job.sender.send(AppEvent::FetchedBranches(branches)).ok();
+                    job.done();
+                }
+                Ok(Err(e)) => {
+                    job.sender.send(AppEvent::Error(e.to_string())).ok();
+                    job.failed();
+                }
+                Err(e) => {
+                    job.sender.send(AppEvent::Error(e.to_string())).ok();
+                    job.failed();
                 }
-                _ => {}
             }
         });
     }
 
-    fn fetch_last_commit(&mut self, branch: &str) {
-        let sender = self.sender.clone();
-        let branch = branch.to_owned();
+    fn fetch_diff(&mut self, selected: usize) {
+        let Some(number) = self.state.prs.get(selected).map(|pr| pr.number) else {
+            return;
+        };
         let repo = self.repo.clone();
 
-        self.tasks.spawn(async move {
-            let commit = fetch_last_branch_commit(&repo, &branch)
-                .await
-                .unwrap_or_default();
-            let _ = sender.send(AppEvent::FetchedBranchCommit(branch, commit));
+        self.spawn_job(format!("diff #{number}"), move |job| async move {
+            job.running();
+
+            match fetch_pr_diff(&repo, number).await {
+                Ok(diff) => {
+                    job.sender.send(AppEvent::FetchedDiff(number, diff)).ok();
+                    job.done();
+                }
+                Err(e) => {
+                    job.sender.send(AppEvent::Error(e.to_string())).ok();
+                    job.failed();
+                }
+            }
         });
     }
 
     fn fetch_prs(&mut self) {
-        let sender = self.sender.clone();
         let repo = self.repo.to_string();
 
-        self.tasks.spawn(async move {
-            let prs = fetch_prs(&repo).await.unwrap_or_default();
-            let _ = sender.send(AppEvent::FetchedPRs(prs));
+        self.spawn_job("fetch PRs", move |job| async move {
+            job.running();
+
+            match fetch_prs(&repo).await {
+                Ok(prs) => {
+                    job.sender.send(AppEvent::FetchedPRs(prs)).ok();
+                    job.done();
+                }
+                Err(e) => {
+                    job.sender.send(AppEvent::Error(e.to_string())).ok();
+                    job.failed();
+                }
+            }
         });
     }
 
     fn handle_term_event(&mut self, event: &Event) {
+        if let Event::Key(key) = event {
+            if let Some(selected) = self.action_menu {
+                match key.code {
+                    KeyCode::Up => {
+                        self.action_index = self.action_index.saturating_sub(1);
+                    }
+
+                    KeyCode::Down => {
+                        self.action_index = (self.action_index + 1).min(PrAction::ALL.len() - 1);
+                    }
+
+                    KeyCode::Enter => {
+                        let action = PrAction::ALL[self.action_index];
+                        self.action_menu = None;
+                        self.run_action(selected, action);
+                    }
+
+                    KeyCode::Esc => {
+                        self.action_menu = None;
+                    }
+
+                    _ => {}
+                }
+
+                return;
+            }
+        }
+
         match event {
             Event::Key(key) => match key.code {
                 KeyCode::Char('r') | KeyCode::Char('R') => {
@@ -253,39 +704,104 @@ where {
                 }
 
                 KeyCode::Up => {
-                    if !self.state.prs.is_empty() {
+                    if let Some(index) = self.check_index {
+                        self.check_index = Some(index.saturating_sub(1));
+                    } else if !self.state.prs.is_empty() {
                         let selected =
                             self.table_state
                                 .selected()
                                 .map_or(0, |i| if i > 0 { i - 1 } else { 0 });
                         self.table_state.select(Some(selected));
+
+                        if self.show_diff {
+                            self.fetch_diff(selected);
+                        }
                     }
                 }
 
                 KeyCode::Down => {
-                    let selected = self.table_state.selected().map_or(0, |i| {
-                        if i < self.state.prs.len() - 1 {
-                            i + 1
-                        } else {
-                            self.state.prs.len() - 1
-                        }
-                    });
+                    if let (Some(expanded), Some(index)) = (self.expanded, self.check_index) {
+                        let max = self.state.prs[expanded].checks.len().saturating_sub(1);
+                        self.check_index = Some((index + 1).min(max));
+                    } else {
+                        let selected = self.table_state.selected().map_or(0, |i| {
+                            if i < self.state.prs.len() - 1 {
+                                i + 1
+                            } else {
+                                self.state.prs.len() - 1
+                            }
+                        });
 
-                    self.table_state.select(Some(selected));
+                        self.table_state.select(Some(selected));
+
+                        if self.show_diff {
+                            self.fetch_diff(selected);
+                        }
+                    }
                 }
 
                 KeyCode::Enter => {
-                    if let Some(selected) = self.table_state.selected() {
+                    if let (Some(expanded), Some(index)) = (self.expanded, self.check_index) {
+                        if let Some(CheckData::StatusContext { target_url, .. }) =
+                            self.state.prs[expanded].checks.get(index)
+                        {
+                            self.open_url(target_url.clone());
+                        }
+                    } else if let Some(selected) = self.table_state.selected() {
                         self.open_externally(selected);
                     }
                 }
 
-                KeyCode::Char('s') => {
+                KeyCode::Char('c') => {
+                    if let Some(selected) = self.table_state.selected() {
+                        if self.expanded == Some(selected) {
+                            self.expanded = None;
+                            self.check_index = None;
+                        } else if let Some(pr) = self.state.prs.get(selected) {
+                            self.expanded = Some(selected);
+                            self.check_index = if pr.checks.is_empty() {
+                                None
+                            } else {
+                                Some(0)
+                            };
+                        }
+                    }
+                }
+
+                KeyCode::Char('m') => {
                     if let Some(selected) = self.table_state.selected() {
-                        self.rebase(selected);
+                        if let Some(pr) = self.state.prs.get(selected) {
+                            if pr.mergeable == Mergeable::Ok && !pr.draft {
+                                self.action_menu = Some(selected);
+                                self.action_index = 0;
+                            }
+                        }
+                    }
+                }
+
+                KeyCode::Char('d') | KeyCode::Tab => {
+                    self.show_diff = !self.show_diff;
+                    self.diff_scroll = 0;
+
+                    if self.show_diff {
+                        if let Some(selected) = self.table_state.selected() {
+                            self.fetch_diff(selected);
+                        }
                     }
                 }
 
+                KeyCode::PageUp if self.show_diff => {
+                    self.diff_scroll = self.diff_scroll.saturating_sub(10);
+                }
+
+                KeyCode::PageDown if self.show_diff => {
+                    self.diff_scroll = self.diff_scroll.saturating_add(10);
+                }
+
+                KeyCode::Char('x') => {
+                    self.cancel_latest_job();
+                }
+
                 _ => {}
             },
             _ => {}
@@ -295,13 +811,71 @@ where {
     fn handle_app_event(&mut self, event: AppEvent) {
         match event {
             AppEvent::FetchedPRs(prs) => {
-                self.state.prs = prs.to_owned();
+                if self.notify {
+                    let transitions = prs
+                        .iter()
+                        .filter_map(|pr| {
+                            let new_status = pr.check_status();
+                            let old_status = self.state.check_states.get(&pr.number).copied();
+
+                            is_notable_transition(old_status, new_status)
+                                .then(|| (pr.number, pr.title.clone(), new_status))
+                        })
+                        .collect::<Vec<_>>();
+
+                    for (number, title, status) in transitions {
+                        self.notify_check_transition(number, title, status);
+                    }
+                }
+
+                self.state.check_states = prs
+                    .iter()
+                    .map(|pr| (pr.number, pr.check_status()))
+                    .collect();
+                self.state.prs = prs;
+                self.expanded = None;
+                self.check_index = None;
+                self.action_menu = None;
+                self.action_index = 0;
+
+                // The refresh may have returned fewer rows than before (a PR
+                // merged, closed, or got filtered out) — clamp the selection
+                // so every `table_state.selected()` lookup stays in bounds.
+                if self.state.prs.is_empty() {
+                    self.table_state.select(None);
+                } else if let Some(selected) = self.table_state.selected() {
+                    if selected >= self.state.prs.len() {
+                        self.table_state.select(Some(self.state.prs.len() - 1));
+                    }
+                }
+
+                self.fetch_branches();
+            }
+
+            AppEvent::FetchedBranches(branches) => {
+                self.state.branches.extend(branches);
+            }
+
+            AppEvent::FetchedDiff(number, diff) => {
+                self.state.diffs.insert(number, diff);
             }
 
-            AppEvent::FetchedBranchCommit(branch, commit) => {
-                self.state
-                    .branches
-                    .insert(branch.to_owned(), commit.to_owned());
+            AppEvent::JobProgress { id, label, state } => {
+                let finished_at = matches!(state, JobState::Done | JobState::Failed)
+                    .then(Instant::now);
+
+                if finished_at.is_some() {
+                    self.abort_handles.remove(&id);
+                }
+
+                self.state.jobs.insert(
+                    id,
+                    Job {
+                        label,
+                        state,
+                        finished_at,
+                    },
+                );
             }
 
             AppEvent::Error(msg) => self.state.error_message = Some(msg.split('\n').join(" ")),
@@ -319,6 +893,7 @@ pub async fn fetch_prs(repo: &str) -> Result<Vec<PullRequest>, Box<dyn std::erro
         .arg("number,title,mergeable,headRefName,baseRefName,baseRefOid,isDraft,url,statusCheckRollup")
         .arg("-R")
         .arg(repo)
+        .kill_on_drop(true)
         .output()
         .await?;
 
@@ -334,13 +909,14 @@ pub async fn fetch_prs(repo: &str) -> Result<Vec<PullRequest>, Box<dyn std::erro
     Ok(prs)
 }
 
-pub async fn fetch_last_branch_commit(
-    repo: &str,
-    branch: &str,
-) -> Result<String, Box<dyn std::error::Error>> {
+pub async fn fetch_pr_diff(repo: &str, number: i32) -> Result<String, Box<dyn std::error::Error>> {
     let output = AsyncCommand::new("gh")
-        .arg("api")
-        .arg(format!("/repos/{repo}/branches/{branch}"))
+        .arg("pr")
+        .arg("diff")
+        .arg(number.to_string())
+        .arg("-R")
+        .arg(repo)
+        .kill_on_drop(true)
         .output()
         .await?;
 
@@ -349,11 +925,100 @@ pub async fn fetch_last_branch_commit(
         return Err(format!("gh command failed: {}", err_msg).into());
     }
 
-    let json_str = String::from_utf8(output.stdout)?;
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn is_notable_transition(old: Option<CheckProgress>, new: CheckProgress) -> bool {
+    match (old, new) {
+        (Some(CheckProgress::Failure), CheckProgress::Failure) => false,
+        (_, CheckProgress::Failure) => true,
+        (Some(CheckProgress::Pending), CheckProgress::Success) => true,
+        _ => false,
+    }
+}
 
-    let branch: Branch = serde_json::from_str(&json_str)?;
+#[test]
+fn test_is_notable_transition() {
+    // A still-failing PR shouldn't re-notify every poll.
+    assert!(!is_notable_transition(
+        Some(CheckProgress::Failure),
+        CheckProgress::Failure
+    ));
+
+    // Any fresh failure is notable, regardless of what preceded it.
+    assert!(is_notable_transition(None, CheckProgress::Failure));
+    assert!(is_notable_transition(
+        Some(CheckProgress::Pending),
+        CheckProgress::Failure
+    ));
+    assert!(is_notable_transition(
+        Some(CheckProgress::Success),
+        CheckProgress::Failure
+    ));
+
+    // Checks finishing up is notable.
+    assert!(is_notable_transition(
+        Some(CheckProgress::Pending),
+        CheckProgress::Success
+    ));
+
+    // Anything else (first sighting already passing/pending, no change) is not.
+    assert!(!is_notable_transition(None, CheckProgress::Success));
+    assert!(!is_notable_transition(None, CheckProgress::Pending));
+    assert!(!is_notable_transition(
+        Some(CheckProgress::Success),
+        CheckProgress::Success
+    ));
+    assert!(!is_notable_transition(
+        Some(CheckProgress::Pending),
+        CheckProgress::Pending
+    ));
+}
+
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(vertical[1])[1]
+}
 
-    Ok(branch.commit.sha)
+fn colorize_diff(diff: &str) -> Text<'static> {
+    Text::from(
+        diff.lines()
+            .map(|line| {
+                let style = if line.starts_with("@@") {
+                    Style::default().fg(Color::Cyan)
+                } else if line.starts_with("diff --git")
+                    || line.starts_with("+++")
+                    || line.starts_with("---")
+                    || line.starts_with("index ")
+                {
+                    Style::default().add_modifier(Modifier::BOLD)
+                } else if line.starts_with('+') {
+                    Style::default().fg(Color::Green)
+                } else if line.starts_with('-') {
+                    Style::default().fg(Color::Red)
+                } else {
+                    Style::default()
+                };
+
+                Line::from(line.to_owned()).style(style)
+            })
+            .collect::<Vec<_>>(),
+    )
 }
 
 pub async fn fetch_current_repo() -> Result<String, Box<dyn std::error::Error>> {
@@ -388,7 +1053,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     };
 
     let mut terminal = setup_terminal()?;
-    App::new(repo).run(&mut terminal).await?;
+    App::new(repo, args.notify).run(&mut terminal).await?;
     restore_terminal(terminal)?;
     Ok(())
 }
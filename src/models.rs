@@ -50,6 +50,7 @@ pub struct PullRequest {
     pub checks: Vec<CheckData>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum CheckProgress {
     Pending,
     Success,